@@ -7,16 +7,17 @@ use anyhow::{Context as _, Result, anyhow};
 use async_tungstenite::tungstenite::{
     client::IntoClientRequest,
     error::Error as WebsocketError,
-    http::{HeaderValue, Request, StatusCode},
+    http::{HeaderName, HeaderValue, Request, StatusCode},
 };
 use clock::SystemClock;
+use collections::HashMap;
 use credentials_provider::CredentialsProvider;
 use feature_flags::FeatureFlagAppExt as _;
 use futures::{
     AsyncReadExt, FutureExt, SinkExt, Stream, StreamExt, TryFutureExt as _, TryStreamExt,
     channel::oneshot, future::BoxFuture,
 };
-use gpui::{App, AsyncApp, Entity, Global, Task, WeakEntity, actions};
+use gpui::{App, AsyncApp, Entity, FutureExt as _, Global, Task, WeakEntity, actions};
 use http_client::{HttpClient, HttpClientWithUrl, http};
 use parking_lot::RwLock;
 use postage::watch;
@@ -36,7 +37,7 @@ use std::{
     path::PathBuf,
     sync::{
         Arc, LazyLock, Weak,
-        atomic::{AtomicU64, Ordering},
+        atomic::{AtomicU64, AtomicUsize, Ordering},
     },
     time::{Duration, Instant},
 };
@@ -45,7 +46,7 @@ use telemetry::Telemetry;
 use thiserror::Error;
 use tokio::net::TcpStream;
 use url::Url;
-use util::{ConnectionResult, ResultExt};
+use util::{ConnectionResult, ResultExt, post_inc};
 
 pub use rpc::*;
 pub use telemetry_events::Event;
@@ -78,6 +79,11 @@ pub static ZED_ALWAYS_ACTIVE: LazyLock<bool> =
 pub const INITIAL_RECONNECTION_DELAY: Duration = Duration::from_millis(500);
 pub const MAX_RECONNECTION_DELAY: Duration = Duration::from_secs(30);
 pub const CONNECTION_TIMEOUT: Duration = Duration::from_secs(20);
+pub const DIAL_TIMEOUT: Duration = Duration::from_secs(10);
+pub const KEEPALIVE_TIMEOUT: Duration = Duration::from_secs(10);
+const DRAIN_POLL_INTERVAL: Duration = Duration::from_millis(50);
+const RECONNECTION_EVENTS_CAPACITY: usize = 32;
+const STATUS_CHANGES_CAPACITY: usize = 32;
 
 actions!(
     client,
@@ -197,9 +203,12 @@ pub struct Client {
     peer: Arc<Peer>,
     http: Arc<HttpClientWithUrl>,
     telemetry: Arc<Telemetry>,
+    clock: Arc<dyn SystemClock>,
     credentials_provider: ClientCredentialsProvider,
     state: RwLock<ClientState>,
     handler_set: parking_lot::Mutex<ProtoMessageHandlerSet>,
+    reconnect_policy: parking_lot::Mutex<Box<dyn ReconnectPolicy>>,
+    in_flight_requests: Arc<AtomicUsize>,
 }
 
 #[derive(Error, Debug)]
@@ -256,6 +265,8 @@ pub enum Status {
     Reconnecting,
     ReconnectionError {
         next_reconnection: Instant,
+        /// The number of failed reconnection attempts so far, starting at 1.
+        attempt: u32,
     },
 }
 
@@ -291,10 +302,91 @@ impl Status {
     }
 }
 
+/// An event emitted by the reconnection loop that runs after the connection is lost.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ReconnectionEvent {
+    AttemptStarted,
+    Succeeded,
+    Failed { error: String },
+}
+
+/// Describes an outgoing RPC, as reported to the observer set with [`Client::set_rpc_observer`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RpcEvent {
+    pub kind: RpcEventKind,
+    pub type_name: &'static str,
+    pub started: Instant,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RpcEventKind {
+    /// A request was sent and is awaiting its response.
+    Request,
+    /// A message was sent without expecting a response.
+    Send,
+    /// The response to a request arrived, or the request failed.
+    Response { duration: Duration, success: bool },
+}
+
+type RpcObserver = dyn Fn(RpcEvent) + Send + Sync;
+
+/// Counts a request as in flight until dropped.
+struct InFlightRequest(Arc<AtomicUsize>);
+
+impl InFlightRequest {
+    fn new(count: &Arc<AtomicUsize>) -> Self {
+        count.fetch_add(1, Ordering::SeqCst);
+        Self(count.clone())
+    }
+}
+
+impl Drop for InFlightRequest {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Decides how long the client waits before each reconnection attempt.
+pub trait ReconnectPolicy: Send {
+    /// Returns the delay before the next attempt. `attempt` starts at 1 for the first retry,
+    /// and `last` is the delay returned for the previous attempt, or zero if there was none.
+    fn next_delay(&mut self, attempt: u32, last: Duration) -> Duration;
+}
+
+/// Starts at [`INITIAL_RECONNECTION_DELAY`] and doubles on every attempt, up to
+/// [`MAX_RECONNECTION_DELAY`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ExponentialBackoff;
+
+impl ReconnectPolicy for ExponentialBackoff {
+    fn next_delay(&mut self, attempt: u32, last: Duration) -> Duration {
+        if attempt <= 1 {
+            INITIAL_RECONNECTION_DELAY
+        } else {
+            cmp::min(last * 2, MAX_RECONNECTION_DELAY)
+        }
+    }
+}
+
 struct ClientState {
     credentials: Option<Credentials>,
     status: (watch::Sender<Status>, watch::Receiver<Status>),
+    reconnection_event_txs: Vec<smol::channel::Sender<ReconnectionEvent>>,
+    status_change_txs: Vec<smol::channel::Sender<(Status, Instant)>>,
+    status_observers: HashMap<usize, Arc<dyn Fn(Status) + Send + Sync>>,
+    next_status_observer_id: usize,
+    /// Whether connections should be declared read-only, as requested by
+    /// [`Client::connect_read_only`].
+    read_only_requested: bool,
+    /// Whether the current connection was declared read-only.
+    read_only: bool,
+    draining: bool,
+    keepalive_interval: Option<Duration>,
+    dial_timeout: Duration,
+    extra_connect_headers: Vec<(HeaderName, HeaderValue)>,
+    rpc_observer: Option<Arc<RpcObserver>>,
     _reconnect_task: Option<Task<()>>,
+    _keepalive_task: Option<Task<()>>,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -389,7 +481,19 @@ impl Default for ClientState {
         Self {
             credentials: None,
             status: watch::channel_with(Status::SignedOut),
+            reconnection_event_txs: Vec::new(),
+            status_change_txs: Vec::new(),
+            status_observers: HashMap::default(),
+            next_status_observer_id: 0,
+            read_only_requested: false,
+            read_only: false,
+            draining: false,
+            keepalive_interval: None,
+            dial_timeout: DIAL_TIMEOUT,
+            extra_connect_headers: Vec::new(),
+            rpc_observer: None,
             _reconnect_task: None,
+            _keepalive_task: None,
         }
     }
 }
@@ -403,6 +507,10 @@ pub enum Subscription {
         client: Weak<Client>,
         id: TypeId,
     },
+    StatusObserver {
+        client: Weak<Client>,
+        id: usize,
+    },
 }
 
 impl Drop for Subscription {
@@ -421,6 +529,11 @@ impl Drop for Subscription {
                     let _ = state.message_handlers.remove(id);
                 }
             }
+            Subscription::StatusObserver { client, id } => {
+                if let Some(client) = client.upgrade() {
+                    client.state.write().status_observers.remove(id);
+                }
+            }
         }
     }
 }
@@ -534,11 +647,14 @@ impl Client {
         Arc::new(Self {
             id: AtomicU64::new(0),
             peer: Peer::new(0),
-            telemetry: Telemetry::new(clock, http.clone(), cx),
+            telemetry: Telemetry::new(clock.clone(), http.clone(), cx),
+            clock,
             http,
             credentials_provider: ClientCredentialsProvider::new(cx),
             state: Default::default(),
             handler_set: Default::default(),
+            reconnect_policy: parking_lot::Mutex::new(Box::new(ExponentialBackoff)),
+            in_flight_requests: Default::default(),
         })
     }
 
@@ -565,6 +681,58 @@ impl Client {
         self
     }
 
+    /// Sets how long to wait for the TCP (or proxy) connection to the RPC endpoint to be
+    /// established, leaving the rest of [`CONNECTION_TIMEOUT`] for the WebSocket handshake.
+    pub fn set_dial_timeout(&self, timeout: Duration) -> &Self {
+        self.state.write().dial_timeout = timeout;
+        self
+    }
+
+    /// Sets additional headers to send with the WebSocket upgrade request, e.g. for an
+    /// authenticating reverse proxy. Headers set by the client itself take precedence.
+    ///
+    /// Fails without changing the current headers if a header is invalid, or if it's
+    /// `Authorization` or starts with `x-zed-`, which are reserved for the client.
+    pub fn set_extra_connect_headers(&self, headers: Vec<(String, String)>) -> Result<&Self> {
+        let headers = headers
+            .into_iter()
+            .map(|(name, value)| {
+                let name = HeaderName::try_from(name.as_str())
+                    .with_context(|| format!("invalid header name: {name:?}"))?;
+                anyhow::ensure!(
+                    name != http::header::AUTHORIZATION && !name.as_str().starts_with("x-zed-"),
+                    "header {name} is reserved"
+                );
+                let value = HeaderValue::from_str(&value)
+                    .with_context(|| format!("invalid value for header {name}"))?;
+                Ok((name, value))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        self.state.write().extra_connect_headers = headers;
+        Ok(self)
+    }
+
+    /// Sets a callback that is notified when RPC requests and messages are sent, and when
+    /// responses arrive. Useful for collecting per-message latency metrics.
+    pub fn set_rpc_observer(&self, observer: Box<RpcObserver>) -> &Self {
+        self.state.write().rpc_observer = Some(observer.into());
+        self
+    }
+
+    /// Enables an application-level keepalive: while connected, the client pings the server
+    /// every `interval` and treats the connection as lost if no response arrives within
+    /// [`KEEPALIVE_TIMEOUT`]. Takes effect on the next connection.
+    pub fn set_keepalive_interval(&self, interval: Option<Duration>) -> &Self {
+        self.state.write().keepalive_interval = interval;
+        self
+    }
+
+    /// Replaces the policy used to space out reconnection attempts after the connection is lost.
+    pub fn set_reconnect_policy(&self, policy: impl ReconnectPolicy + 'static) -> &Self {
+        *self.reconnect_policy.lock() = Box::new(policy);
+        self
+    }
+
     pub fn global(cx: &App) -> Arc<Self> {
         cx.global::<GlobalClient>().0.clone()
     }
@@ -580,6 +748,23 @@ impl Client {
             .map(|credentials| credentials.user_id)
     }
 
+    /// Returns whether the client holds credentials and is authenticated or connected.
+    ///
+    /// Only in-memory state is consulted, so this is cheap enough to call while rendering.
+    pub fn is_authenticated(&self) -> bool {
+        let state = self.state.read();
+        state.credentials.is_some()
+            && matches!(
+                *state.status.1.borrow(),
+                Status::Authenticated | Status::Reauthenticated | Status::Connected { .. }
+            )
+    }
+
+    /// Returns the credentials currently held in memory, without reading the credentials provider.
+    pub fn current_credentials(&self) -> Option<Credentials> {
+        self.state.read().credentials.clone()
+    }
+
     pub fn peer_id(&self) -> Option<PeerId> {
         if let Status::Connected { peer_id, .. } = &*self.status().borrow() {
             Some(*peer_id)
@@ -588,57 +773,124 @@ impl Client {
         }
     }
 
+    /// Returns whether the current connection was established with [`Client::connect_read_only`].
+    pub fn is_read_only(&self) -> bool {
+        self.state.read().read_only
+    }
+
     pub fn status(&self) -> watch::Receiver<Status> {
         self.state.read().status.1.clone()
     }
 
+    /// Returns a stream of every status transition, stamped with the time it occurred.
+    ///
+    /// The stream is bounded: when a consumer falls behind, the oldest transitions are dropped.
+    pub fn status_changes(&self) -> impl Stream<Item = (Status, Instant)> + use<> {
+        let (tx, rx) = smol::channel::bounded(STATUS_CHANGES_CAPACITY);
+        self.state.write().status_change_txs.push(tx);
+        rx
+    }
+
+    /// Registers a callback that is invoked with the new status on every status transition,
+    /// after the [`Client::status`] watch has been updated.
+    ///
+    /// The callback is deregistered when the returned [`Subscription`] is dropped.
+    pub fn add_status_observer(
+        self: &Arc<Self>,
+        observer: impl Fn(Status) + Send + Sync + 'static,
+    ) -> Subscription {
+        let mut state = self.state.write();
+        let id = post_inc(&mut state.next_status_observer_id);
+        state.status_observers.insert(id, Arc::new(observer));
+        Subscription::StatusObserver {
+            client: Arc::downgrade(self),
+            id,
+        }
+    }
+
+    /// Returns a stream of the events produced by the reconnection loop.
+    ///
+    /// The stream is bounded: when a consumer falls behind, the oldest events are dropped.
+    pub fn reconnection_events(&self) -> impl Stream<Item = ReconnectionEvent> + use<> {
+        let (tx, rx) = smol::channel::bounded(RECONNECTION_EVENTS_CAPACITY);
+        self.state.write().reconnection_event_txs.push(tx);
+        rx
+    }
+
+    fn emit_reconnection_event(&self, event: ReconnectionEvent) {
+        self.state
+            .write()
+            .reconnection_event_txs
+            .retain(|tx| tx.force_send(event.clone()).is_ok());
+    }
+
     fn set_status(self: &Arc<Self>, status: Status, cx: &AsyncApp) {
         log::info!("set status on client {}: {:?}", self.id(), status);
         let mut state = self.state.write();
         *state.status.0.borrow_mut() = status;
+        let changed_at = self.clock.utc_now();
+        state
+            .status_change_txs
+            .retain(|tx| tx.force_send((status, changed_at)).is_ok());
+        let observers = state.status_observers.values().cloned().collect::<Vec<_>>();
+        state._keepalive_task = None;
+        state.read_only = false;
 
         match status {
-            Status::Connected { .. } => {
+            Status::Connected { connection_id, .. } => {
                 state._reconnect_task = None;
+                state.read_only = state.read_only_requested;
+                state._keepalive_task = state
+                    .keepalive_interval
+                    .map(|interval| self.keepalive(connection_id, interval, cx));
             }
             Status::ConnectionLost => {
                 let client = self.clone();
                 state._reconnect_task = Some(cx.spawn(async move |cx| {
                     let mut rng = StdRng::from_os_rng();
 
-                    let mut delay = INITIAL_RECONNECTION_DELAY;
+                    let mut attempt = 0;
+                    let mut delay = Duration::ZERO;
                     loop {
-                        match client.connect(cx).await {
+                        client.emit_reconnection_event(ReconnectionEvent::AttemptStarted);
+                        let error = match client.connect(cx).await {
                             ConnectionResult::Timeout => {
-                                log::error!("client connect attempt timed out")
+                                log::error!("client connect attempt timed out");
+                                "connection timed out".to_string()
                             }
                             ConnectionResult::ConnectionReset => {
-                                log::error!("client connect attempt reset")
+                                log::error!("client connect attempt reset");
+                                "connection reset".to_string()
                             }
                             ConnectionResult::Result(r) => {
                                 if let Err(error) = r {
                                     log::error!("failed to connect: {error}");
+                                    format!("{error:#}")
                                 } else {
+                                    client.emit_reconnection_event(ReconnectionEvent::Succeeded);
                                     break;
                                 }
                             }
-                        }
+                        };
+                        client.emit_reconnection_event(ReconnectionEvent::Failed { error });
 
                         if matches!(
                             *client.status().borrow(),
                             Status::AuthenticationError | Status::ConnectionError
                         ) {
+                            attempt += 1;
+                            delay = client.reconnect_policy.lock().next_delay(attempt, delay);
                             client.set_status(
                                 Status::ReconnectionError {
                                     next_reconnection: Instant::now() + delay,
+                                    attempt,
                                 },
                                 cx,
                             );
                             let jitter = Duration::from_millis(
-                                rng.random_range(0..delay.as_millis() as u64),
+                                rng.random_range(0..=delay.as_millis() as u64),
                             );
                             cx.background_executor().timer(delay + jitter).await;
-                            delay = cmp::min(delay * 2, MAX_RECONNECTION_DELAY);
                         } else {
                             break;
                         }
@@ -647,10 +899,47 @@ impl Client {
             }
             Status::SignedOut | Status::UpgradeRequired => {
                 self.telemetry.set_authenticated_user_info(None, false);
+                state.read_only_requested = false;
                 state._reconnect_task.take();
             }
             _ => {}
         }
+
+        // Release the lock first, so that observers are free to call back into the client.
+        drop(state);
+        for observer in observers {
+            observer(status);
+        }
+    }
+
+    fn keepalive(
+        self: &Arc<Self>,
+        connection_id: ConnectionId,
+        interval: Duration,
+        cx: &AsyncApp,
+    ) -> Task<()> {
+        let client = self.clone();
+        cx.spawn(async move |cx| {
+            loop {
+                cx.background_executor().timer(interval).await;
+                let mut timeout =
+                    futures::FutureExt::fuse(cx.background_executor().timer(KEEPALIVE_TIMEOUT));
+                let alive = futures::select_biased! {
+                    response = client.peer.request(connection_id, proto::Ping {}).fuse() => {
+                        response.is_ok()
+                    },
+                    _ = timeout => false,
+                };
+                if !alive {
+                    log::error!("keepalive ping timed out");
+                    // Update the status before disconnecting, so that the IO task doesn't
+                    // mistake the disconnect for a sign out.
+                    client.set_status(Status::ConnectionLost, cx);
+                    client.peer.disconnect(connection_id);
+                    break;
+                }
+            }
+        })
     }
 
     pub fn subscribe_to_entity<T>(
@@ -836,6 +1125,35 @@ impl Client {
         Ok(credentials)
     }
 
+    /// Re-reads the stored credentials from the credentials provider and adopts them, without
+    /// going through the browser sign-in flow.
+    ///
+    /// The status only changes when the client is signed out or failed to authenticate or
+    /// connect, in which case it moves to `Authenticated` or `Reauthenticated` like
+    /// [`Client::sign_in`] would. Any other status, such as a live connection or a sign-in or
+    /// connection attempt in progress, is left untouched.
+    pub async fn refresh_credentials(self: &Arc<Self>, cx: &AsyncApp) -> Result<Credentials> {
+        let credentials = self
+            .credentials_provider
+            .read_credentials(cx)
+            .await
+            .context("no stored credentials")?;
+
+        self.set_id(credentials.user_id);
+        self.state.write().credentials = Some(credentials.clone());
+        let status = *self.status().borrow();
+        match status {
+            Status::SignedOut => self.set_status(Status::Authenticated, cx),
+            Status::AuthenticationError
+            | Status::ConnectionError
+            | Status::ConnectionLost
+            | Status::ReconnectionError { .. } => self.set_status(Status::Reauthenticated, cx),
+            _ => {}
+        }
+
+        Ok(credentials)
+    }
+
     /// Performs a sign-in and also (optionally) connects to Collab.
     ///
     /// Only Zed staff automatically connect to Collab.
@@ -918,6 +1236,32 @@ impl Client {
         self.connect_with_credentials(credentials, cx).await
     }
 
+    /// Connects like [`Client::connect`], but declares the connection as read-only so that
+    /// the server can restrict what this client is allowed to do.
+    ///
+    /// The connection stays read-only across reconnects until the client signs out. If this
+    /// attempt fails, later connections are not read-only. If the client is already connected
+    /// or a connection attempt is in progress, that connection is reused as is and is not
+    /// marked read-only.
+    pub async fn connect_read_only(self: &Arc<Self>, cx: &AsyncApp) -> ConnectionResult<()> {
+        let starts_connection = !matches!(
+            *self.status().borrow(),
+            Status::Connected { .. }
+                | Status::Connecting
+                | Status::Reconnecting
+                | Status::UpgradeRequired
+        );
+        if starts_connection {
+            self.state.write().read_only_requested = true;
+        }
+        let result = self.connect(cx).await;
+        // Don't let a failed attempt make later connections read-only.
+        if starts_connection && !matches!(result, ConnectionResult::Result(Ok(()))) {
+            self.state.write().read_only_requested = false;
+        }
+        result
+    }
+
     async fn connect_with_credentials(
         self: &Arc<Self>,
         credentials: Credentials,
@@ -1118,6 +1462,11 @@ impl Client {
         let rpc_url = self.rpc_url(http, release_channel);
         let system_id = self.telemetry.system_id();
         let metrics_id = self.telemetry.metrics_id();
+        let read_only = self.state.read().read_only_requested;
+        let (dial_timeout, extra_connect_headers) = {
+            let state = self.state.read();
+            (state.dial_timeout, state.extra_connect_headers.clone())
+        };
         cx.spawn(async move |cx| {
             use HttpOrHttps::*;
 
@@ -1147,7 +1496,9 @@ impl Client {
                     })
                 }
             })?
-            .await?;
+            .with_timeout(dial_timeout, cx.background_executor())
+            .await
+            .map_err(|_| anyhow!("timed out connecting to rpc endpoint {}", rpc_url))??;
 
             log::info!("connected to rpc endpoint {}", rpc_url);
 
@@ -1188,6 +1539,14 @@ impl Client {
             if let Some(metrics_id) = metrics_id {
                 request_headers.insert("x-zed-metrics-id", HeaderValue::from_str(&metrics_id)?);
             }
+            if read_only {
+                request_headers.insert("x-zed-read-only", HeaderValue::from_static("true"));
+            }
+            for (name, value) in extra_connect_headers {
+                if !request_headers.contains_key(&name) {
+                    request_headers.insert(name, value);
+                }
+            }
 
             let (stream, _) = async_tungstenite::tokio::client_async_tls_with_connector_and_config(
                 request,
@@ -1385,6 +1744,31 @@ impl Client {
         }
     }
 
+    /// Signs out like [`Client::sign_out`], but first stops sending new requests and waits up to
+    /// `drain_timeout` for the ones already in flight to complete.
+    pub async fn sign_out_graceful(self: &Arc<Self>, cx: &AsyncApp, drain_timeout: Duration) {
+        self.state.write().draining = true;
+        // Stop draining even if this future is dropped before signing out.
+        let stop_draining = util::defer({
+            let this = self.clone();
+            move || this.state.write().draining = false
+        });
+        let drained = async {
+            while self.in_flight_requests.load(Ordering::SeqCst) > 0 {
+                cx.background_executor().timer(DRAIN_POLL_INTERVAL).await;
+            }
+        };
+        if drained
+            .with_timeout(drain_timeout, cx.background_executor())
+            .await
+            .is_err()
+        {
+            log::warn!("timed out waiting for in-flight requests before signing out");
+        }
+        drop(stop_draining);
+        self.sign_out(cx).await;
+    }
+
     pub fn disconnect(self: &Arc<Self>, cx: &AsyncApp) {
         self.peer.teardown();
         self.set_status(Status::SignedOut, cx);
@@ -1403,9 +1787,62 @@ impl Client {
         }
     }
 
+    /// Like [`Client::connection_id`], but fails while [`Client::sign_out_graceful`] is draining,
+    /// so that no new requests or messages are sent. Responses can still be sent.
+    fn outgoing_connection_id(&self) -> Result<ConnectionId> {
+        anyhow::ensure!(!self.state.read().draining, "signing out");
+        self.connection_id()
+    }
+
     pub fn send<T: EnvelopedMessage>(&self, message: T) -> Result<()> {
         log::debug!("rpc send. client_id:{}, name:{}", self.id(), T::NAME);
-        self.peer.send(self.connection_id()?, message)
+        let connection_id = self.outgoing_connection_id()?;
+        self.observe_send(T::NAME);
+        self.peer.send(connection_id, message)
+    }
+
+    /// Notifies the RPC observer, if any, that a message is being sent.
+    fn observe_send(&self, type_name: &'static str) {
+        if let Some(observer) = self.rpc_observer() {
+            observer(RpcEvent {
+                kind: RpcEventKind::Send,
+                type_name,
+                started: Instant::now(),
+            });
+        }
+    }
+
+    fn rpc_observer(&self) -> Option<Arc<RpcObserver>> {
+        self.state.read().rpc_observer.clone()
+    }
+
+    /// Notifies the RPC observer, if any, that a request started, and returns a callback that
+    /// notifies it once the request finishes. The request counts as in flight until the callback
+    /// is called or dropped.
+    fn observe_request(&self, type_name: &'static str) -> impl FnOnce(bool) + use<> {
+        let in_flight = InFlightRequest::new(&self.in_flight_requests);
+        let observer = self.rpc_observer();
+        let started = Instant::now();
+        if let Some(observer) = &observer {
+            observer(RpcEvent {
+                kind: RpcEventKind::Request,
+                type_name,
+                started,
+            });
+        }
+        move |success| {
+            drop(in_flight);
+            if let Some(observer) = observer {
+                observer(RpcEvent {
+                    kind: RpcEventKind::Response {
+                        duration: started.elapsed(),
+                        success,
+                    },
+                    type_name,
+                    started,
+                });
+            }
+        }
     }
 
     pub fn request<T: RequestMessage>(
@@ -1426,11 +1863,19 @@ impl Client {
             client_id,
             T::NAME
         );
+        let in_flight = InFlightRequest::new(&self.in_flight_requests);
         let response = self
-            .connection_id()
+            .outgoing_connection_id()
             .map(|conn_id| self.peer.request_stream(conn_id, request));
         async move {
-            let response = response?.await;
+            // The request stays in flight until its stream ends or is dropped.
+            let response = response?.await.map(move |stream| {
+                let mut in_flight = Some(in_flight);
+                stream.chain(futures::stream::poll_fn(move |_| {
+                    in_flight.take();
+                    std::task::Poll::Ready(None)
+                }))
+            });
             log::debug!(
                 "rpc request finish. client_id:{}. name:{}",
                 client_id,
@@ -1450,11 +1895,19 @@ impl Client {
             client_id,
             T::NAME
         );
-        let response = self
-            .connection_id()
-            .map(|conn_id| self.peer.request_envelope(conn_id, request));
+        let response = self.outgoing_connection_id().map(|conn_id| {
+            let finish = self.observe_request(T::NAME);
+            (self.peer.request_envelope(conn_id, request), finish)
+        });
         async move {
-            let response = response?.await;
+            let response = match response {
+                Ok((response, finish)) => {
+                    let response = response.await;
+                    finish(response.is_ok());
+                    response
+                }
+                Err(error) => Err(error),
+            };
             log::debug!(
                 "rpc request finish. client_id:{}. name:{}",
                 client_id,
@@ -1475,11 +1928,22 @@ impl Client {
             client_id,
             request_type
         );
-        let response = self
-            .connection_id()
-            .map(|conn_id| self.peer.request_dynamic(conn_id, envelope, request_type));
+        let response = self.outgoing_connection_id().map(|conn_id| {
+            let finish = self.observe_request(request_type);
+            (
+                self.peer.request_dynamic(conn_id, envelope, request_type),
+                finish,
+            )
+        });
         async move {
-            let response = response?.await;
+            let response = match response {
+                Ok((response, finish)) => {
+                    let response = response.await;
+                    finish(response.is_ok());
+                    response
+                }
+                Err(error) => Err(error),
+            };
             log::debug!(
                 "rpc request finish. client_id:{}. name:{}",
                 client_id,
@@ -1541,7 +2005,8 @@ impl ProtoClient for Client {
 
     fn send(&self, envelope: proto::Envelope, message_type: &'static str) -> Result<()> {
         log::debug!("rpc send. client_id:{}, name:{}", self.id(), message_type);
-        let connection_id = self.connection_id()?;
+        let connection_id = self.outgoing_connection_id()?;
+        self.observe_send(message_type);
         self.peer.send_dynamic(connection_id, envelope)
     }
 
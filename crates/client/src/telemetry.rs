@@ -45,7 +45,7 @@ struct TelemetryState {
     is_staff: Option<bool>,
     first_event_date_time: Option<Instant>,
     event_coalescer: EventCoalescer,
-    max_queue_size: usize,
+    flush_strategy: FlushStrategy,
     worktrees_with_project_type_events_sent: HashSet<WorktreeId>,
 
     os_name: String,
@@ -56,6 +56,27 @@ struct TelemetryState {
 const MAX_QUEUE_LEN: usize = 50;
 
 const FLUSH_INTERVAL: Duration = Duration::from_secs(60 * 5);
+
+/// Controls when queued events are sent to the server.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FlushStrategy {
+    /// Send every event as soon as it is reported.
+    Immediate,
+    /// Send events once `max_events` are queued, or `max_interval` after the first one was queued.
+    Batched {
+        max_events: usize,
+        max_interval: Duration,
+    },
+}
+
+impl Default for FlushStrategy {
+    fn default() -> Self {
+        Self::Batched {
+            max_events: MAX_QUEUE_LEN,
+            max_interval: FLUSH_INTERVAL,
+        }
+    }
+}
 static ZED_CLIENT_CHECKSUM_SEED: LazyLock<Option<Vec<u8>>> = LazyLock::new(|| {
     option_env!("ZED_CLIENT_CHECKSUM_SEED")
         .map(|s| s.as_bytes().into())
@@ -122,7 +143,7 @@ impl Telemetry {
             is_staff: None,
             first_event_date_time: None,
             event_coalescer: EventCoalescer::new(clock.clone()),
-            max_queue_size: MAX_QUEUE_LEN,
+            flush_strategy: FlushStrategy::default(),
             worktrees_with_project_type_events_sent: HashSet::new(),
 
             os_version: None,
@@ -214,6 +235,10 @@ impl Telemetry {
         state.os_name = os_name();
     }
 
+    pub fn set_flush_strategy(self: &Arc<Self>, flush_strategy: FlushStrategy) {
+        self.state.lock().flush_strategy = flush_strategy;
+    }
+
     pub fn metrics_enabled(self: &Arc<Self>) -> bool {
         let state = self.state.lock();
         let enabled = state.settings.metrics;
@@ -366,13 +391,22 @@ impl Telemetry {
             return;
         }
 
-        if state.flush_events_task.is_none() {
-            let this = self.clone();
-            state.flush_events_task = Some(self.executor.spawn(async move {
-                this.executor.timer(FLUSH_INTERVAL).await;
-                this.flush_events().detach();
-            }));
-        }
+        let max_events = match state.flush_strategy {
+            FlushStrategy::Immediate => 1,
+            FlushStrategy::Batched {
+                max_events,
+                max_interval,
+            } => {
+                if state.flush_events_task.is_none() {
+                    let this = self.clone();
+                    state.flush_events_task = Some(self.executor.spawn(async move {
+                        this.executor.timer(max_interval).await;
+                        this.flush_events().detach();
+                    }));
+                }
+                max_events
+            }
+        };
 
         let date_time = self.clock.utc_now();
 
@@ -394,7 +428,7 @@ impl Telemetry {
             event,
         });
 
-        if state.installation_id.is_some() && state.events_queue.len() >= state.max_queue_size {
+        if state.installation_id.is_some() && state.events_queue.len() >= max_events {
             drop(state);
             self.flush_events().detach();
         }
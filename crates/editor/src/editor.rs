@@ -7701,14 +7701,19 @@ impl Editor {
                     let end_of_line = Point::new(row.0, snapshot.line_len(row));
                     let next_line_row = row.next_row();
                     let indent = snapshot.indent_size_for_line(next_line_row);
-                    let start_of_next_line = Point::new(next_line_row.0, indent.len);
+                    let next_line_is_blank = snapshot.line_len(next_line_row) == indent.len;
+                    // Without whitespace insertion, a whitespace-only line is kept as it is.
+                    let start_of_next_line = if next_line_is_blank && !insert_whitespace {
+                        Point::new(next_line_row.0, 0)
+                    } else {
+                        Point::new(next_line_row.0, indent.len)
+                    };
 
-                    let replace =
-                        if snapshot.line_len(next_line_row) > indent.len && insert_whitespace {
-                            " "
-                        } else {
-                            ""
-                        };
+                    let replace = if !next_line_is_blank && insert_whitespace {
+                        " "
+                    } else {
+                        ""
+                    };
 
                     this.buffer.update(cx, |buffer, cx| {
                         buffer.edit([(end_of_line..start_of_next_line, replace)], None, cx)
@@ -8978,8 +8983,38 @@ impl Editor {
                 let (row_indent, row_comment_delimiters, row_rewrap_prefix) =
                     indent_and_prefix_for_row(row);
 
-                let has_indent_change = row_indent != current_range_indent;
-                let has_comment_change = row_comment_delimiters != current_range_comment_delimiters;
+                // The continuation lines of a block comment whose opening line also contains text
+                // (e.g. `/** Foo` followed by ` * bar`) are wrapped together with that line.
+                let continues_block_comment =
+                    match (&current_range_comment_delimiters, &row_comment_delimiters) {
+                        (
+                            Some(CommentFormat::BlockCommentWithStart(config)),
+                            Some(CommentFormat::BlockLine(prefix)),
+                        ) => {
+                            let start_indent =
+                                buffer.indent_size_for_line(MultiBufferRow(current_range_start));
+                            let start_line = buffer
+                                .text_for_range(
+                                    Point::new(current_range_start, start_indent.len)
+                                        ..Point::new(
+                                            current_range_start,
+                                            buffer.line_len(MultiBufferRow(current_range_start)),
+                                        ),
+                                )
+                                .collect::<String>();
+                            let start_line = start_line.trim_end();
+                            config.prefix.as_ref() == prefix
+                                && row_indent.len == current_range_indent.len + config.tab_size
+                                && start_line != config.start.as_ref()
+                                && !start_line.ends_with(config.end.as_ref())
+                        }
+                        _ => false,
+                    };
+
+                let has_indent_change =
+                    row_indent != current_range_indent && !continues_block_comment;
+                let has_comment_change = row_comment_delimiters != current_range_comment_delimiters
+                    && !continues_block_comment;
 
                 let has_boundary_change = has_comment_change
                     || row_rewrap_prefix.is_some()
@@ -1,5 +1,6 @@
 use editor::{Bias, Direction, Editor, display_map::ToDisplayPoint, movement};
 use gpui::{Context, Window, actions};
+use language::TransactionId;
 
 use crate::{Vim, state::Mode};
 
@@ -13,6 +14,24 @@ actions!(
     ]
 );
 
+/// How the most recent entry in the change list was made.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum ChangeKind {
+    /// The change was typed during an insert (or replace) mode session.
+    ///
+    /// Moving the cursor during the session splits it into several undo groups.
+    /// `transaction_id` is the first transaction of the session, so undoing back to it reverts
+    /// the whole session. It's `None` if the session hasn't started a transaction.
+    Insert {
+        transaction_id: Option<TransactionId>,
+    },
+    /// The change was made outside of insert and replace mode. This is usually a normal or
+    /// visual mode command such as an operator. Edits that don't come from vim, such as
+    /// formatting, LSP edits or collaborators' edits, also land here when they happen outside
+    /// of an insert session.
+    Operator,
+}
+
 pub(crate) fn register(editor: &mut Editor, cx: &mut Context<Vim>) {
     Vim::action(editor, cx, |vim, _: &ChangeListOlder, window, cx| {
         vim.move_to_change(Direction::Prev, window, cx);
@@ -48,7 +67,21 @@ impl Vim {
         });
     }
 
+    /// Returns how the most recent change in this editor was made, if any change was recorded.
+    /// This is based on the vim mode at the time of the edit, not on what made the edit.
+    pub(crate) fn last_change_kind(&self) -> Option<ChangeKind> {
+        self.last_change_kind
+    }
+
     pub(crate) fn push_to_change_list(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.last_change_kind = Some(if matches!(self.mode, Mode::Insert | Mode::Replace) {
+            ChangeKind::Insert {
+                transaction_id: self.insert_session_tx,
+            }
+        } else {
+            ChangeKind::Operator
+        });
+
         let Some((new_positions, buffer)) = self.update_editor(cx, |vim, editor, cx| {
             let (map, selections) = editor.selections.all_adjusted_display(cx);
             let buffer = editor.buffer().clone();
@@ -957,6 +957,20 @@ pub(crate) struct CommandRange {
 }
 
 impl CommandRange {
+    pub(crate) fn current_line() -> Self {
+        CommandRange {
+            start: Position::CurrentLine { offset: 0 },
+            end: None,
+        }
+    }
+
+    pub(crate) fn whole_buffer() -> Self {
+        CommandRange {
+            start: Position::Line { row: 1, offset: 0 },
+            end: Some(Position::LastLine { offset: 0 }),
+        }
+    }
+
     fn head(&self) -> &Position {
         self.end.as_ref().unwrap_or(&self.start)
     }
@@ -1934,6 +1948,7 @@ impl ShellExec {
 
         util::set_pre_exec_to_start_new_session(&mut process);
         let is_read = self.is_read;
+        let is_filter = self.range.is_some() && !self.is_read;
 
         let task = cx.spawn_in(window, async move |vim, cx| {
             let Some(mut running) = process.spawn().log_err() else {
@@ -1970,6 +1985,25 @@ impl ShellExec {
                 .log_err();
                 return;
             };
+            // Leave the buffer untouched when a filter fails, and surface its stderr instead.
+            if is_filter && !output.status.success() {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                let message = match output.status.code() {
+                    Some(code) => format!("shell command exited with {code}: {}", stderr.trim()),
+                    None => format!("shell command was terminated: {}", stderr.trim()),
+                };
+                vim.update_in(cx, |vim, window, cx| {
+                    vim.cancel_running_command(window, cx);
+                    let Some(workspace) = vim.workspace(window) else {
+                        return;
+                    };
+                    workspace.update(cx, |workspace, cx| {
+                        Err::<(), _>(anyhow::anyhow!(message)).notify_err(workspace, cx);
+                    });
+                })
+                .log_err();
+                return;
+            }
             let mut text = String::new();
             if needs_newline_prefix {
                 text.push('\n');
@@ -1,10 +1,11 @@
 use crate::{Vim, motion::Motion, object::Object, state::Mode};
-use collections::HashMap;
+use collections::{BTreeSet, HashMap};
 use editor::SelectionEffects;
 use editor::{Bias, Editor, display_map::ToDisplayPoint};
 use gpui::actions;
 use gpui::{Context, Window};
-use language::SelectionGoal;
+use language::{Point, SelectionGoal};
+use multi_buffer::MultiBufferRow;
 
 #[derive(PartialEq, Eq)]
 pub(crate) enum IndentDirection {
@@ -13,6 +14,12 @@ pub(crate) enum IndentDirection {
     Auto,
 }
 
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum RetabDirection {
+    TabsToSpaces,
+    SpacesToTabs,
+}
+
 actions!(
     vim,
     [
@@ -21,7 +28,11 @@ actions!(
         /// Decreases indentation of selected lines.
         Outdent,
         /// Automatically adjusts indentation based on syntax.
-        AutoIndent
+        AutoIndent,
+        /// Converts leading tabs to spaces using the buffer's tab size.
+        TabsToSpaces,
+        /// Converts leading spaces to tabs using the buffer's tab size.
+        SpacesToTabs
     ]
 );
 
@@ -82,6 +93,14 @@ pub(crate) fn register(editor: &mut Editor, cx: &mut Context<Vim>) {
             vim.switch_mode(Mode::Normal, true, window, cx)
         }
     });
+
+    Vim::action(editor, cx, |vim, _: &TabsToSpaces, window, cx| {
+        vim.retab(RetabDirection::TabsToSpaces, window, cx);
+    });
+
+    Vim::action(editor, cx, |vim, _: &SpacesToTabs, window, cx| {
+        vim.retab(RetabDirection::SpacesToTabs, window, cx);
+    });
 }
 
 impl Vim {
@@ -161,4 +180,143 @@ impl Vim {
             });
         });
     }
+
+    fn retab(&mut self, direction: RetabDirection, window: &mut Window, cx: &mut Context<Self>) {
+        self.record_current_action(cx);
+        let count = Vim::take_count(cx);
+        let forced_motion = Vim::take_forced_motion(cx);
+        if !self.mode.is_visual() {
+            self.retab_motion(
+                Motion::CurrentLine,
+                count,
+                forced_motion,
+                direction,
+                window,
+                cx,
+            );
+            return;
+        }
+
+        self.store_visual_marks(window, cx);
+        self.update_editor(cx, |vim, editor, cx| {
+            editor.transact(window, cx, |editor, window, cx| {
+                let original_positions = vim.save_selection_starts(editor, cx);
+                retab_selected_lines(editor, direction, cx);
+                vim.restore_selection_cursors(editor, window, cx, original_positions);
+            });
+        });
+        self.switch_mode(Mode::Normal, true, window, cx)
+    }
+
+    pub(crate) fn retab_motion(
+        &mut self,
+        motion: Motion,
+        times: Option<usize>,
+        forced_motion: bool,
+        direction: RetabDirection,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.stop_recording(cx);
+        self.update_editor(cx, |_, editor, cx| {
+            let text_layout_details = editor.text_layout_details(window);
+            editor.transact(window, cx, |editor, window, cx| {
+                let mut selection_starts: HashMap<_, _> = Default::default();
+                editor.change_selections(SelectionEffects::no_scroll(), window, cx, |s| {
+                    s.move_with(|map, selection| {
+                        let anchor = map.display_point_to_anchor(selection.head(), Bias::Right);
+                        selection_starts.insert(selection.id, anchor);
+                        motion.expand_selection(
+                            map,
+                            selection,
+                            times,
+                            &text_layout_details,
+                            forced_motion,
+                        );
+                    });
+                });
+                retab_selected_lines(editor, direction, cx);
+                editor.change_selections(SelectionEffects::no_scroll(), window, cx, |s| {
+                    s.move_with(|map, selection| {
+                        let anchor = selection_starts.remove(&selection.id).unwrap();
+                        selection.collapse_to(anchor.to_display_point(map), SelectionGoal::None);
+                    });
+                });
+            });
+        });
+    }
+
+    pub(crate) fn retab_object(
+        &mut self,
+        object: Object,
+        around: bool,
+        direction: RetabDirection,
+        times: Option<usize>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.stop_recording(cx);
+        self.update_editor(cx, |_, editor, cx| {
+            editor.transact(window, cx, |editor, window, cx| {
+                let mut original_positions: HashMap<_, _> = Default::default();
+                editor.change_selections(SelectionEffects::no_scroll(), window, cx, |s| {
+                    s.move_with(|map, selection| {
+                        let anchor = map.display_point_to_anchor(selection.head(), Bias::Right);
+                        original_positions.insert(selection.id, anchor);
+                        object.expand_selection(map, selection, around, times);
+                    });
+                });
+                retab_selected_lines(editor, direction, cx);
+                editor.change_selections(SelectionEffects::no_scroll(), window, cx, |s| {
+                    s.move_with(|map, selection| {
+                        let anchor = original_positions.remove(&selection.id).unwrap();
+                        selection.collapse_to(anchor.to_display_point(map), SelectionGoal::None);
+                    });
+                });
+            });
+        });
+    }
+}
+
+/// Rewrites the leading whitespace of every line touched by a selection, leaving the
+/// rest of each line untouched. Mixed indentation is measured by its display width.
+fn retab_selected_lines(editor: &mut Editor, direction: RetabDirection, cx: &mut Context<Editor>) {
+    let snapshot = editor.buffer().read(cx).snapshot(cx);
+    let mut rows = BTreeSet::new();
+    for selection in editor.selections.all::<Point>(cx) {
+        let mut end_row = selection.end.row;
+        if selection.end.column == 0 && end_row > selection.start.row {
+            end_row -= 1;
+        }
+        rows.extend(selection.start.row..=end_row);
+    }
+
+    let edits = rows
+        .into_iter()
+        .filter_map(|row| {
+            let start = Point::new(row, 0);
+            let indent_len = snapshot.indent_size_for_line(MultiBufferRow(row)).len;
+            let indent_range = start..Point::new(row, indent_len);
+            let indent = snapshot
+                .text_for_range(indent_range.clone())
+                .collect::<String>();
+            let tab_size = snapshot.language_settings_at(start, cx).tab_size.get();
+            let width = indent.chars().fold(0, |width, c| {
+                if c == '\t' {
+                    (width / tab_size + 1) * tab_size
+                } else {
+                    width + 1
+                }
+            });
+            let new_indent = match direction {
+                RetabDirection::TabsToSpaces => " ".repeat(width as usize),
+                RetabDirection::SpacesToTabs => {
+                    "\t".repeat((width / tab_size) as usize)
+                        + &" ".repeat((width % tab_size) as usize)
+                }
+            };
+            (new_indent != indent).then_some((indent_range, new_indent))
+        })
+        .collect::<Vec<_>>();
+    editor.edit(edits, cx);
 }
@@ -16,7 +16,8 @@ use std::sync::Arc;
 
 use crate::{
     Vim,
-    indent::IndentDirection,
+    change_list::ChangeKind,
+    indent::{IndentDirection, RetabDirection},
     motion::{self, Motion, first_non_whitespace, next_line_end, right},
     object::Object,
     state::{Mark, Mode, Operator},
@@ -28,10 +29,22 @@ use editor::Editor;
 use editor::{Anchor, SelectionEffects};
 use editor::{Bias, ToPoint};
 use editor::{display_map::ToDisplayPoint, movement};
-use gpui::{Context, Window, actions};
-use language::{Point, SelectionGoal};
+use gpui::{Action, Context, Window, actions};
+use language::{CharKind, Point, SelectionGoal, ToOffset};
 use log::error;
 use multi_buffer::MultiBufferRow;
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+/// Selects every occurrence of the word under the cursor, creating a cursor at each.
+#[derive(Clone, Debug, Deserialize, JsonSchema, PartialEq, Action)]
+#[action(namespace = vim)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct SelectAllMatchesOfWord {
+    /// Also select occurrences that are part of a larger word.
+    #[serde(default)]
+    partial_word: bool,
+}
 
 actions!(
     vim,
@@ -80,20 +93,34 @@ actions!(
         ConvertToUpperCase,
         /// Converts selected text to lowercase.
         ConvertToLowerCase,
+        /// Toggles the case of the current line.
+        ChangeCaseLine,
+        /// Converts the current line to uppercase.
+        ConvertLineToUpperCase,
+        /// Converts the current line to lowercase.
+        ConvertLineToLowerCase,
         /// Applies ROT13 cipher to selected text.
         ConvertToRot13,
         /// Applies ROT47 cipher to selected text.
         ConvertToRot47,
+        /// Pastes line-wise text after the current line, matching its indentation.
+        PasteAfterReindent,
+        /// Pastes line-wise text before the current line, matching its indentation.
+        PasteBeforeReindent,
         /// Toggles comments for selected lines.
         ToggleComments,
         /// Shows the current location in the file.
         ShowLocation,
+        /// Shows the cursor's column, line, word and byte position in the file.
+        ShowDetailedLocation,
         /// Undoes the last change.
         Undo,
         /// Redoes the last undone change.
         Redo,
         /// Undoes all changes to the most recently changed line.
         UndoLastLine,
+        /// Restores the changes reverted by the last `UndoLastLine`.
+        RedoLastLine,
     ]
 );
 
@@ -111,11 +138,28 @@ pub(crate) fn register(editor: &mut Editor, cx: &mut Context<Vim>) {
     Vim::action(editor, cx, Vim::convert_to_upper_case);
     Vim::action(editor, cx, Vim::convert_to_lower_case);
     Vim::action(editor, cx, Vim::convert_to_rot13);
+    Vim::action(editor, cx, |vim, _: &ChangeCaseLine, window, cx| {
+        vim.convert_current_line(ConvertTarget::OppositeCase, window, cx)
+    });
+    Vim::action(editor, cx, |vim, _: &ConvertLineToUpperCase, window, cx| {
+        vim.convert_current_line(ConvertTarget::UpperCase, window, cx)
+    });
+    Vim::action(editor, cx, |vim, _: &ConvertLineToLowerCase, window, cx| {
+        vim.convert_current_line(ConvertTarget::LowerCase, window, cx)
+    });
     Vim::action(editor, cx, Vim::convert_to_rot47);
     Vim::action(editor, cx, Vim::yank_line);
     Vim::action(editor, cx, Vim::toggle_comments);
     Vim::action(editor, cx, Vim::paste);
+    Vim::action(editor, cx, |vim, _: &PasteAfterReindent, window, cx| {
+        vim.paste_reindent(false, window, cx)
+    });
+    Vim::action(editor, cx, |vim, _: &PasteBeforeReindent, window, cx| {
+        vim.paste_reindent(true, window, cx)
+    });
     Vim::action(editor, cx, Vim::show_location);
+    Vim::action(editor, cx, Vim::show_detailed_location);
+    Vim::action(editor, cx, Vim::select_all_matches_of_word);
 
     Vim::action(editor, cx, |vim, _: &DeleteLeft, window, cx| {
         vim.record_current_action(cx);
@@ -146,8 +190,19 @@ pub(crate) fn register(editor: &mut Editor, cx: &mut Context<Vim>) {
     });
 
     Vim::action(editor, cx, |vim, _: &HelixCollapseSelection, window, cx| {
+        let count = Vim::take_count(cx);
+        Vim::take_forced_motion(cx);
         vim.update_editor(cx, |_, editor, cx| {
+            // With a count, only the selection at that (1-based) position is kept.
+            let kept_selection = count.and_then(|count| {
+                let selections = editor.selections.all_anchors(cx);
+                let index = count.clamp(1, selections.len()) - 1;
+                selections.get(index).cloned()
+            });
             editor.change_selections(SelectionEffects::no_scroll(), window, cx, |s| {
+                if let Some(selection) = kept_selection {
+                    s.select_anchors(vec![selection]);
+                }
                 s.move_with(|map, selection| {
                     let mut point = selection.head();
                     if !selection.reversed && !selection.is_empty() {
@@ -215,6 +270,13 @@ pub(crate) fn register(editor: &mut Editor, cx: &mut Context<Vim>) {
     });
     Vim::action(editor, cx, |vim, _: &UndoLastLine, window, cx| {
         Vim::take_forced_motion(cx);
+        // Don't keep undoing past the start of an insert session, as that could revert unrelated
+        // changes when the session added or removed lines. Without a known transaction, only
+        // stop once the changed lines stop changing.
+        let insert_tx = match vim.last_change_kind() {
+            Some(ChangeKind::Insert { transaction_id }) => transaction_id,
+            _ => None,
+        };
         vim.update_editor(cx, |vim, editor, cx| {
             let snapshot = editor.buffer().read(cx).snapshot(cx);
             let Some(last_change) = editor.change_list.last_before_grouping() else {
@@ -277,7 +339,9 @@ pub(crate) fn register(editor: &mut Editor, cx: &mut Context<Vim>) {
                     if !line_changed {
                         break;
                     }
-                    if undone_tx == vim.undo_last_line_tx {
+                    if undone_tx == vim.undo_last_line_tx
+                        || (insert_tx.is_some() && undone_tx == insert_tx)
+                    {
                         break;
                     }
                 }
@@ -327,6 +391,22 @@ pub(crate) fn register(editor: &mut Editor, cx: &mut Context<Vim>) {
             });
         });
     });
+    Vim::action(editor, cx, |vim, _: &RedoLastLine, window, cx| {
+        Vim::take_count(cx);
+        Vim::take_forced_motion(cx);
+        vim.update_editor(cx, |vim, editor, cx| {
+            // `UndoLastLine` applies its changes as a transaction of its own, so reverting that
+            // transaction restores the line. If anything was edited since, there's nothing to do.
+            let Some(undo_last_line_tx) = vim.undo_last_line_tx else {
+                return;
+            };
+            if editor.buffer().read(cx).last_transaction_id(cx) != Some(undo_last_line_tx) {
+                return;
+            }
+            editor.undo(&editor::actions::Undo, window, cx);
+            vim.undo_last_line_tx = None;
+        });
+    });
 
     repeat::register(editor, cx);
     scroll::register(editor, cx);
@@ -376,6 +456,22 @@ impl Vim {
                 window,
                 cx,
             ),
+            Some(Operator::TabsToSpaces) => self.retab_motion(
+                motion,
+                times,
+                forced_motion,
+                RetabDirection::TabsToSpaces,
+                window,
+                cx,
+            ),
+            Some(Operator::SpacesToTabs) => self.retab_motion(
+                motion,
+                times,
+                forced_motion,
+                RetabDirection::SpacesToTabs,
+                window,
+                cx,
+            ),
             Some(Operator::ShellCommand) => {
                 self.shell_command_motion(motion, times, forced_motion, window, cx)
             }
@@ -459,6 +555,22 @@ impl Vim {
                 Some(Operator::AutoIndent) => {
                     self.indent_object(object, around, IndentDirection::Auto, times, window, cx)
                 }
+                Some(Operator::TabsToSpaces) => self.retab_object(
+                    object,
+                    around,
+                    RetabDirection::TabsToSpaces,
+                    times,
+                    window,
+                    cx,
+                ),
+                Some(Operator::SpacesToTabs) => self.retab_object(
+                    object,
+                    around,
+                    RetabDirection::SpacesToTabs,
+                    times,
+                    window,
+                    cx,
+                ),
                 Some(Operator::ShellCommand) => {
                     self.shell_command_object(object, around, window, cx);
                 }
@@ -726,8 +838,15 @@ impl Vim {
         let count = Vim::take_count(cx).unwrap_or(1);
         Vim::take_forced_motion(cx);
         self.update_editor(cx, |_, editor, cx| {
-            editor.transact(window, cx, |editor, _, cx| {
+            editor.transact(window, cx, |editor, window, cx| {
                 let selections = editor.selections.all::<Point>(cx);
+                let snapshot = editor.buffer().read(cx).snapshot(cx);
+                // Anchor after the cursor, so that it stays on its line when the new lines are
+                // inserted right before it (e.g. when it's at the start of the line).
+                let original_positions = selections
+                    .iter()
+                    .map(|selection| (selection.id, snapshot.anchor_after(selection.head())))
+                    .collect::<HashMap<_, _>>();
 
                 let selection_start_rows: BTreeSet<u32> = selections
                     .into_iter()
@@ -741,6 +860,15 @@ impl Vim {
                     })
                     .collect::<Vec<_>>();
                 editor.edit(edits, cx);
+
+                editor.change_selections(SelectionEffects::no_scroll(), window, cx, |s| {
+                    s.move_with(|map, selection| {
+                        if let Some(anchor) = original_positions.get(&selection.id) {
+                            selection
+                                .collapse_to(anchor.to_display_point(map), SelectionGoal::None);
+                        }
+                    });
+                });
             });
         });
     }
@@ -804,11 +932,43 @@ impl Vim {
             times -= 1;
         }
 
+        let is_visual = self.mode.is_visual();
         self.update_editor(cx, |_, editor, cx| {
             editor.transact(window, cx, |editor, window, cx| {
+                // With a count, the cursor ends up at the first seam rather than the last one.
+                let mut first_seams = None;
                 for _ in 0..times {
+                    let snapshot = editor.buffer().read(cx).snapshot(cx);
+                    let max_row = snapshot.max_point().row;
+                    let selections = editor.selections.all::<Point>(cx);
+                    if !is_visual && selections.iter().all(|s| s.end.row >= max_row) {
+                        break;
+                    }
+                    if first_seams.is_none() {
+                        first_seams = Some(
+                            selections
+                                .iter()
+                                .map(|selection| {
+                                    let row = selection.start.row;
+                                    let anchor = snapshot.anchor_before(Point::new(
+                                        row,
+                                        snapshot.line_len(MultiBufferRow(row)),
+                                    ));
+                                    anchor..anchor
+                                })
+                                .collect::<Vec<_>>(),
+                        );
+                    }
                     editor.join_lines_impl(insert_whitespace, window, cx)
                 }
+                if !is_visual
+                    && times > 1
+                    && let Some(first_seams) = first_seams
+                {
+                    editor.change_selections(Default::default(), window, cx, |s| {
+                        s.select_anchor_ranges(first_seams)
+                    });
+                }
             })
         });
         if self.mode.is_visual() {
@@ -872,6 +1032,127 @@ impl Vim {
         });
     }
 
+    /// Like vim's `g Ctrl-G`. When tabs or multi-byte characters make the byte column differ
+    /// from the screen column, both are shown as `Col <byte>-<screen>`.
+    fn show_detailed_location(
+        &mut self,
+        _: &ShowDetailedLocation,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        Vim::take_count(cx);
+        Vim::take_forced_motion(cx);
+        self.update_editor(cx, |vim, editor, cx| {
+            let selection = editor.selections.newest_anchor();
+            let Some((buffer, point, _)) = editor
+                .buffer()
+                .read(cx)
+                .point_to_buffer_point(selection.head(), cx)
+            else {
+                return;
+            };
+            let snapshot = buffer.read(cx).snapshot();
+            let tab_size = snapshot.settings_at(point, cx).tab_size.get() as usize;
+
+            let line_len = snapshot.line_len(point.row);
+            let column = if line_len == 0 {
+                "0 of 0".to_string()
+            } else {
+                let line_start = Point::new(point.row, 0);
+                let mut screen_column = 0;
+                for c in snapshot.chars_for_range(line_start..point) {
+                    screen_column += if c == '\t' {
+                        tab_size - screen_column % tab_size
+                    } else {
+                        1
+                    };
+                }
+                let byte_column = point.column as usize + 1;
+                let screen_column = screen_column + 1;
+                if byte_column == screen_column {
+                    format!("{byte_column} of {line_len}")
+                } else {
+                    format!("{byte_column}-{screen_column} of {line_len}")
+                }
+            };
+
+            let offset = point.to_offset(&snapshot);
+            let total_bytes = snapshot.len();
+            let mut words = 0;
+            let mut words_before_cursor = 0;
+            let mut in_word = false;
+            let mut position = 0;
+            for c in snapshot.chars() {
+                let is_word_char = !c.is_whitespace();
+                if is_word_char && !in_word {
+                    words += 1;
+                    if position <= offset {
+                        words_before_cursor = words;
+                    }
+                }
+                in_word = is_word_char;
+                position += c.len_utf8();
+            }
+            let (byte, percentage) = if total_bytes == 0 {
+                (0, 0)
+            } else {
+                (offset + 1, offset * 100 / total_bytes)
+            };
+
+            vim.status_label = Some(
+                format!(
+                    "Col {}; Line {} of {}; Word {} of {}; Byte {} of {} --{}%--",
+                    column,
+                    point.row + 1,
+                    snapshot.max_point().row + 1,
+                    words_before_cursor,
+                    words,
+                    byte,
+                    total_bytes,
+                    percentage,
+                )
+                .into(),
+            );
+            cx.notify();
+        });
+    }
+
+    fn select_all_matches_of_word(
+        &mut self,
+        action: &SelectAllMatchesOfWord,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        Vim::take_count(cx);
+        Vim::take_forced_motion(cx);
+        self.update_editor(cx, |_, editor, cx| {
+            let snapshot = editor.buffer().read(cx).snapshot(cx);
+            let head = editor.selections.newest::<usize>(cx).head();
+            let (word_range, kind) = snapshot.surrounding_word(head, false);
+            if kind != Some(CharKind::Word) || word_range.is_empty() {
+                return;
+            }
+            let word = snapshot
+                .text_for_range(word_range.clone())
+                .collect::<String>();
+            let ranges = snapshot
+                .text()
+                .match_indices(word.as_str())
+                .map(|(start, _)| start..start + word.len())
+                .filter(|range| {
+                    action.partial_word
+                        || (!snapshot.is_inside_word(range.start, false)
+                            && !snapshot.is_inside_word(range.end, false))
+                })
+                .collect::<Vec<_>>();
+
+            editor.set_clip_at_line_ends(false, cx);
+            editor.change_selections(SelectionEffects::no_scroll(), window, cx, |s| {
+                s.select_ranges(ranges)
+            });
+        });
+    }
+
     fn toggle_comments(&mut self, _: &ToggleComments, window: &mut Window, cx: &mut Context<Self>) {
         self.record_current_action(cx);
         self.store_visual_marks(window, cx);
@@ -6,7 +6,7 @@ use multi_buffer::MultiBufferRow;
 
 use crate::{
     Vim,
-    motion::Motion,
+    motion::{Motion, start_of_line},
     normal::{ChangeCase, ConvertToLowerCase, ConvertToRot13, ConvertToRot47, ConvertToUpperCase},
     object::Object,
     state::Mode,
@@ -77,6 +77,27 @@ impl Vim {
         });
     }
 
+    /// Converts the current line, or `count` lines, leaving the cursor at the start of the
+    /// first one. This is `guu`, `gUU` and `g~~`.
+    pub(crate) fn convert_current_line(
+        &mut self,
+        mode: ConvertTarget,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.record_current_action(cx);
+        let count = Vim::take_count(cx);
+        let forced_motion = Vim::take_forced_motion(cx);
+        self.convert_motion(Motion::CurrentLine, count, forced_motion, mode, window, cx);
+        self.update_editor(cx, |_, editor, cx| {
+            editor.change_selections(SelectionEffects::no_scroll(), window, cx, |s| {
+                s.move_cursors_with(|map, cursor, _| {
+                    (start_of_line(map, false, cursor), SelectionGoal::None)
+                })
+            });
+        });
+    }
+
     pub fn convert_object(
         &mut self,
         object: Object,
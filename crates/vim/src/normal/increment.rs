@@ -1,14 +1,19 @@
 use editor::{Editor, MultiBufferSnapshot, ToOffset, ToPoint};
-use gpui::{Action, Context, Window};
+use gpui::{Action, Context, Window, actions};
 use language::{Bias, Point};
+use multi_buffer::MultiBufferRow;
+use regex::Regex;
 use schemars::JsonSchema;
 use serde::Deserialize;
-use std::ops::Range;
+use std::{ops::Range, sync::LazyLock};
 
 use crate::{Vim, state::Mode};
 
 const BOOLEAN_PAIRS: &[(&str, &str)] = &[("true", "false"), ("yes", "no"), ("on", "off")];
 
+static ISO_DATE_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\b(\d{4})-(\d{2})-(\d{2})\b").unwrap());
+
 /// Increments the number under the cursor or toggles boolean values.
 #[derive(Clone, Deserialize, JsonSchema, PartialEq, Action)]
 #[action(namespace = vim)]
@@ -27,6 +32,16 @@ struct Decrement {
     step: bool,
 }
 
+actions!(
+    vim,
+    [
+        /// Adds days to the ISO date (YYYY-MM-DD) under the cursor.
+        IncrementDate,
+        /// Subtracts days from the ISO date (YYYY-MM-DD) under the cursor.
+        DecrementDate
+    ]
+);
+
 pub fn register(editor: &mut Editor, cx: &mut Context<Vim>) {
     Vim::action(editor, cx, |vim, action: &Increment, window, cx| {
         vim.record_current_action(cx);
@@ -42,6 +57,18 @@ pub fn register(editor: &mut Editor, cx: &mut Context<Vim>) {
         let step = if action.step { -1 * (count as i32) } else { 0 };
         vim.increment(-(count as i64), step, window, cx)
     });
+    Vim::action(editor, cx, |vim, _: &IncrementDate, window, cx| {
+        vim.record_current_action(cx);
+        let count = Vim::take_count(cx).unwrap_or(1);
+        Vim::take_forced_motion(cx);
+        vim.increment_date(count as i64, window, cx)
+    });
+    Vim::action(editor, cx, |vim, _: &DecrementDate, window, cx| {
+        vim.record_current_action(cx);
+        let count = Vim::take_count(cx).unwrap_or(1);
+        Vim::take_forced_motion(cx);
+        vim.increment_date(-(count as i64), window, cx)
+    });
 }
 
 impl Vim {
@@ -126,6 +153,100 @@ impl Vim {
         });
         self.switch_mode(Mode::Normal, true, window, cx)
     }
+
+    fn increment_date(&mut self, days: i64, window: &mut Window, cx: &mut Context<Self>) {
+        self.update_editor(cx, |_, editor, cx| {
+            let snapshot = editor.buffer().read(cx).snapshot(cx);
+            let edits = editor
+                .selections
+                .all::<Point>(cx)
+                .into_iter()
+                .filter_map(|selection| {
+                    let (range, date) = find_date(&snapshot, selection.head())?;
+                    Some((range, shift_date(date, days)?))
+                })
+                .collect::<Vec<_>>();
+            editor.transact(window, cx, |editor, _, cx| {
+                editor.edit(edits, cx);
+            });
+        });
+    }
+}
+
+/// Finds a valid `YYYY-MM-DD` date on the cursor's line that contains the cursor.
+fn find_date(
+    snapshot: &MultiBufferSnapshot,
+    point: Point,
+) -> Option<(Range<Point>, (i64, u32, u32))> {
+    let line = snapshot
+        .text_for_range(
+            Point::new(point.row, 0)
+                ..Point::new(point.row, snapshot.line_len(MultiBufferRow(point.row))),
+        )
+        .collect::<String>();
+    let column = point.column as usize;
+    let captures = ISO_DATE_REGEX
+        .captures_iter(&line)
+        .find(|captures| captures.get(0).is_some_and(|m| m.range().contains(&column)))?;
+    let full_match = captures.get(0)?;
+    let year = captures[1].parse::<i64>().ok()?;
+    let month = captures[2].parse::<u32>().ok()?;
+    let day = captures[3].parse::<u32>().ok()?;
+    if !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) {
+        return None;
+    }
+    Some((
+        Point::new(point.row, full_match.start() as u32)
+            ..Point::new(point.row, full_match.end() as u32),
+        (year, month, day),
+    ))
+}
+
+fn shift_date((year, month, day): (i64, u32, u32), days: i64) -> Option<String> {
+    let (year, month, day) = civil_from_days(days_from_civil(year, month, day) + days);
+    (0..=9999)
+        .contains(&year)
+        .then(|| format!("{year:04}-{month:02}-{day:02}"))
+}
+
+fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+// Conversions between proleptic Gregorian dates and days since 1970-01-01, from
+// http://howardhinnant.github.io/date_algorithms.html
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = month as i64;
+    let day_of_year =
+        (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days - era * 146097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u32;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    } as u32;
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
 }
 
 fn increment_decimal_string(num: &str, delta: i64) -> String {
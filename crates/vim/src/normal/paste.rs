@@ -1,10 +1,11 @@
 use editor::{DisplayPoint, RowExt, SelectionEffects, display_map::ToDisplayPoint, movement};
 use gpui::{Action, Context, Window};
-use language::{Bias, SelectionGoal};
+use language::{Bias, IndentKind, Point, SelectionGoal};
+use multi_buffer::MultiBufferRow;
 use schemars::JsonSchema;
 use serde::Deserialize;
 use settings::Settings;
-use std::cmp;
+use std::{cmp, iter};
 use vim_mode_setting::HelixModeSetting;
 
 use crate::{
@@ -27,6 +28,28 @@ pub struct Paste {
 
 impl Vim {
     pub fn paste(&mut self, action: &Paste, window: &mut Window, cx: &mut Context<Self>) {
+        self.paste_impl(action.before, action.preserve_clipboard, false, window, cx)
+    }
+
+    /// Pastes like [`Vim::paste`], but shifts line-wise text so that its first line is indented
+    /// like the current line. This is `]p` and `[p`.
+    pub(crate) fn paste_reindent(
+        &mut self,
+        before: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.paste_impl(before, false, true, window, cx)
+    }
+
+    fn paste_impl(
+        &mut self,
+        before: bool,
+        preserve_clipboard: bool,
+        reindent: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
         self.record_current_action(cx);
         self.store_visual_marks(window, cx);
         let count = Vim::take_count(cx).unwrap_or(1);
@@ -52,7 +75,7 @@ impl Vim {
                 let clipboard_selections = clipboard_selections
                     .filter(|sel| sel.len() > 1 && vim.mode != Mode::VisualLine);
 
-                if !action.preserve_clipboard && vim.mode.is_visual() {
+                if !preserve_clipboard && vim.mode.is_visual() {
                     vim.copy_selections_content(editor, MotionKind::for_mode(vim.mode), window, cx);
                 }
 
@@ -90,12 +113,13 @@ impl Vim {
                             .first()
                             .map(|selection| selection.first_line_indent)
                     });
-                let before = action.before || vim.mode == Mode::VisualLine;
+                let before = before || vim.mode == Mode::VisualLine;
 
                 let mut edits = Vec::new();
                 let mut new_selections = Vec::new();
                 let mut original_indent_columns = Vec::new();
                 let mut start_offset = 0;
+                let mut reindented = false;
 
                 for (ix, (selection, preserve)) in selections_to_process.iter().enumerate() {
                     let (mut to_insert, original_indent_column) =
@@ -114,6 +138,22 @@ impl Vim {
                     let line_mode = to_insert.ends_with('\n');
                     let is_multiline = to_insert.contains('\n');
 
+                    if reindent && line_mode {
+                        reindented = true;
+                        let buffer = &display_map.buffer_snapshot;
+                        let start = Point::new(selection.start.to_point(&display_map).row, 0);
+                        let target_indent = buffer.indent_size_for_line(MultiBufferRow(start.row));
+                        let tab_size = buffer.language_settings_at(start, cx).tab_size.get();
+                        let target_width = indent_width(
+                            &buffer
+                                .text_for_range(start..Point::new(start.row, target_indent.len))
+                                .collect::<String>(),
+                            tab_size,
+                        );
+                        to_insert =
+                            reindent_lines(&to_insert, target_width, target_indent.kind, tab_size);
+                    }
+
                     if line_mode && !before {
                         if selection.is_empty() {
                             to_insert =
@@ -172,12 +212,13 @@ impl Vim {
                 }
 
                 let cursor_offset = editor.selections.last::<usize>(cx).head();
-                if editor
-                    .buffer()
-                    .read(cx)
-                    .snapshot(cx)
-                    .language_settings_at(cursor_offset, cx)
-                    .auto_indent_on_paste
+                if !reindented
+                    && editor
+                        .buffer()
+                        .read(cx)
+                        .snapshot(cx)
+                        .language_settings_at(cursor_offset, cx)
+                        .auto_indent_on_paste
                 {
                     editor.edit_with_block_indent(edits, original_indent_columns, cx);
                 } else {
@@ -307,3 +348,43 @@ impl Vim {
         });
     }
 }
+
+/// Shifts the indentation of `text` so that its first line is indented `target_width` columns
+/// deep, keeping the indentation of the following lines relative to it. The new indentation is
+/// written using `kind`, and blank lines are left empty.
+fn reindent_lines(text: &str, target_width: u32, kind: IndentKind, tab_size: u32) -> String {
+    let indent_len = |line: &str| line.chars().take_while(|c| *c == ' ' || *c == '\t').count();
+    let first_line_width = text
+        .lines()
+        .next()
+        .map_or(0, |line| indent_width(&line[..indent_len(line)], tab_size));
+    let mut result = String::with_capacity(text.len());
+    for line in text.split_inclusive('\n') {
+        let indent = indent_len(line);
+        let content = &line[indent..];
+        if !content.trim().is_empty() {
+            let width = (indent_width(&line[..indent], tab_size) + target_width)
+                .saturating_sub(first_line_width);
+            match kind {
+                IndentKind::Tab => {
+                    result.extend(iter::repeat_n('\t', (width / tab_size) as usize));
+                    result.extend(iter::repeat_n(' ', (width % tab_size) as usize));
+                }
+                IndentKind::Space => result.extend(iter::repeat_n(' ', width as usize)),
+            }
+        }
+        result.push_str(content);
+    }
+    result
+}
+
+/// Returns the number of columns `indent` spans, expanding tabs to the next tab stop.
+fn indent_width(indent: &str, tab_size: u32) -> u32 {
+    indent.chars().fold(0, |width, c| {
+        if c == '\t' {
+            (width / tab_size + 1) * tab_size
+        } else {
+            width + 1
+        }
+    })
+}
@@ -88,7 +88,11 @@ actions!(
         /// Moves to the next search match.
         MoveToNextMatch,
         /// Moves to the previous search match.
-        MoveToPreviousMatch
+        MoveToPreviousMatch,
+        /// Repeats the last substitution on the current line, without its flags.
+        RepeatSubstitute,
+        /// Repeats the last substitution with its flags across the whole buffer.
+        RepeatSubstituteGlobal
     ]
 );
 
@@ -101,6 +105,8 @@ pub(crate) fn register(editor: &mut Editor, cx: &mut Context<Vim>) {
     Vim::action(editor, cx, Vim::search_deploy);
     Vim::action(editor, cx, Vim::find_command);
     Vim::action(editor, cx, Vim::replace_command);
+    Vim::action(editor, cx, Vim::repeat_substitute);
+    Vim::action(editor, cx, Vim::repeat_substitute_global);
 }
 
 impl Vim {
@@ -453,6 +459,7 @@ impl Vim {
         cx: &mut Context<Self>,
     ) {
         let replacement = action.replacement.clone();
+        Vim::globals(cx).last_replacement = Some(replacement.clone());
         let Some(((pane, workspace), editor)) = self
             .pane(window, cx)
             .zip(self.workspace(window))
@@ -557,6 +564,56 @@ impl Vim {
         })
         .detach_and_log_err(cx);
     }
+
+    fn repeat_substitute(
+        &mut self,
+        _: &RepeatSubstitute,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        Vim::take_count(cx);
+        Vim::take_forced_motion(cx);
+        let Some(last_replacement) = Vim::globals(cx).last_replacement.clone() else {
+            return;
+        };
+        let replacement = Replacement {
+            search: last_replacement.search,
+            replacement: last_replacement.replacement,
+            case_sensitive: None,
+            flag_n: false,
+            flag_g: false,
+            flag_c: false,
+        };
+        self.replace_command(
+            &ReplaceCommand {
+                range: CommandRange::current_line(),
+                replacement,
+            },
+            window,
+            cx,
+        );
+    }
+
+    fn repeat_substitute_global(
+        &mut self,
+        _: &RepeatSubstituteGlobal,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        Vim::take_count(cx);
+        Vim::take_forced_motion(cx);
+        let Some(replacement) = Vim::globals(cx).last_replacement.clone() else {
+            return;
+        };
+        self.replace_command(
+            &ReplaceCommand {
+                range: CommandRange::whole_buffer(),
+                replacement,
+            },
+            window,
+            cx,
+        );
+    }
 }
 
 impl Replacement {
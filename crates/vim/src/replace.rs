@@ -10,6 +10,7 @@ use editor::{
 };
 use gpui::{Context, Window, actions};
 use language::{Point, SelectionGoal};
+use multi_buffer::MultiBufferRow;
 use std::ops::Range;
 use std::sync::Arc;
 
@@ -40,7 +41,7 @@ pub fn register(editor: &mut Editor, cx: &mut Context<Vim>) {
     });
 }
 
-struct VimExchange;
+pub(crate) struct VimExchange;
 
 impl Vim {
     pub(crate) fn multi_replace(
@@ -168,9 +169,22 @@ impl Vim {
     pub fn exchange_visual(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         self.stop_recording(cx);
         self.update_editor(cx, |vim, editor, cx| {
-            let selection = editor.selections.newest_anchor();
-            let new_range = selection.start..selection.end;
             let snapshot = editor.snapshot(window, cx);
+            let new_range = if vim.mode == Mode::VisualLine {
+                let selection = editor.selections.newest::<Point>(cx);
+                let buffer = &snapshot.buffer_snapshot;
+                let end_row =
+                    if selection.end.row > selection.start.row && selection.end.column == 0 {
+                        selection.end.row - 1
+                    } else {
+                        selection.end.row
+                    };
+                let end = Point::new(end_row, buffer.line_len(MultiBufferRow(end_row)));
+                buffer.anchor_before(Point::new(selection.start.row, 0))..buffer.anchor_before(end)
+            } else {
+                let selection = editor.selections.newest_anchor();
+                selection.start..selection.end
+            };
             vim.exchange_impl(new_range, editor, &snapshot, window, cx);
         });
         self.switch_mode(Mode::Normal, false, window, cx);
@@ -242,7 +256,8 @@ impl Vim {
 
             let mut final_cursor_position = None;
 
-            if previous_range_end < new_range_start || new_range_end < previous_range_start {
+            // Regions that only partially overlap match none of these cases and cancel the exchange.
+            if previous_range_end <= new_range_start || new_range_end <= previous_range_start {
                 let previous_text = text_for(previous_range.clone());
                 let new_text = text_for(new_range.clone());
                 final_cursor_position = Some(new_range.start.to_display_point(snapshot));
@@ -1,6 +1,7 @@
 use crate::command::command_interceptor;
 use crate::motion::MotionKind;
 use crate::normal::repeat::Replayer;
+use crate::normal::search::Replacement;
 use crate::surrounds::SurroundsType;
 use crate::{ToggleMarksView, ToggleRegistersView, UseSystemClipboard, Vim, VimAddon, VimSettings};
 use crate::{motion::Motion, object::Object};
@@ -115,6 +116,8 @@ pub enum Operator {
     Indent,
     Outdent,
     AutoIndent,
+    TabsToSpaces,
+    SpacesToTabs,
     Rewrap,
     ShellCommand,
     Lowercase,
@@ -198,6 +201,7 @@ impl From<String> for Register {
 #[derive(Default)]
 pub struct VimGlobals {
     pub last_find: Option<Motion>,
+    pub last_replacement: Option<Replacement>,
 
     pub dot_recording: bool,
     pub dot_replaying: bool,
@@ -777,7 +781,11 @@ impl VimGlobals {
                     }
                     '"' => {
                         self.registers.insert('"', content.clone());
-                        self.registers.insert('0', content);
+                        if is_yank {
+                            self.registers.insert('0', content);
+                        } else {
+                            self.record_delete(content, kind);
+                        }
                     }
                     _ => {
                         self.registers.insert('"', content.clone());
@@ -802,19 +810,25 @@ impl VimGlobals {
             if is_yank {
                 self.registers.insert('0', content);
             } else {
-                let contains_newline = content.text.contains('\n');
-                if !contains_newline {
-                    self.registers.insert('-', content.clone());
-                }
-                if kind.linewise() || contains_newline {
-                    let mut content = content;
-                    for i in '1'..='9' {
-                        if let Some(moved) = self.registers.insert(i, content) {
-                            content = moved;
-                        } else {
-                            break;
-                        }
-                    }
+                self.record_delete(content, kind);
+            }
+        }
+    }
+
+    /// Stores deleted text in the small delete register `-`, or, for line-wise and multi-line
+    /// deletes, in `1`, shifting the previous contents of `1`-`8` up by one and dropping `9`.
+    fn record_delete(&mut self, content: Register, kind: MotionKind) {
+        let contains_newline = content.text.contains('\n');
+        if !contains_newline {
+            self.registers.insert('-', content.clone());
+        }
+        if kind.linewise() || contains_newline {
+            let mut content = content;
+            for i in '1'..='9' {
+                if let Some(moved) = self.registers.insert(i, content) {
+                    content = moved;
+                } else {
+                    break;
                 }
             }
         }
@@ -994,6 +1008,8 @@ impl Operator {
             Operator::Jump { line: false } => "`",
             Operator::Indent => ">",
             Operator::AutoIndent => "eq",
+            Operator::TabsToSpaces => "tts",
+            Operator::SpacesToTabs => "stt",
             Operator::ShellCommand => "sh",
             Operator::Rewrap => "gq",
             Operator::ReplaceWithRegister => "gR",
@@ -1057,6 +1073,8 @@ impl Operator {
             | Operator::Indent
             | Operator::Outdent
             | Operator::AutoIndent
+            | Operator::TabsToSpaces
+            | Operator::SpacesToTabs
             | Operator::ShellCommand
             | Operator::Lowercase
             | Operator::Uppercase
@@ -1079,6 +1097,8 @@ impl Operator {
             | Operator::Indent
             | Operator::Outdent
             | Operator::AutoIndent
+            | Operator::TabsToSpaces
+            | Operator::SpacesToTabs
             | Operator::Lowercase
             | Operator::Uppercase
             | Operator::OppositeCase
@@ -17,6 +17,7 @@ mod surrounds;
 mod visual;
 
 use anyhow::Result;
+use change_list::ChangeKind;
 use collections::HashMap;
 use editor::{
     Anchor, Bias, Editor, EditorEvent, EditorSettings, HideMouseCursorOrigin, SelectionEffects,
@@ -195,6 +196,10 @@ actions!(
         PushOutdent,
         /// Starts an auto-indent operation.
         PushAutoIndent,
+        /// Starts a tabs-to-spaces operation.
+        PushTabsToSpaces,
+        /// Starts a spaces-to-tabs operation.
+        PushSpacesToTabs,
         /// Starts a rewrap operation.
         PushRewrap,
         /// Starts a shell command operation.
@@ -368,9 +373,13 @@ pub(crate) struct Vim {
     pub(crate) stored_visual_mode: Option<(Mode, Vec<bool>)>,
 
     pub(crate) current_tx: Option<TransactionId>,
+    /// The first transaction of the current insert or replace mode session. Unlike
+    /// `current_tx`, it's kept when the cursor moves and the session's undo groups split.
+    insert_session_tx: Option<TransactionId>,
     pub(crate) current_anchor: Option<Selection<Anchor>>,
     pub(crate) undo_modes: HashMap<TransactionId, Mode>,
     pub(crate) undo_last_line_tx: Option<TransactionId>,
+    last_change_kind: Option<ChangeKind>,
 
     selected_register: Option<char>,
     pub search: SearchState,
@@ -418,7 +427,9 @@ impl Vim {
 
             stored_visual_mode: None,
             current_tx: None,
+            insert_session_tx: None,
             undo_last_line_tx: None,
+            last_change_kind: None,
             current_anchor: None,
             undo_modes: HashMap::default(),
 
@@ -495,6 +506,12 @@ impl Vim {
 
         vim.update(cx, |_, cx| {
             Vim::action(editor, cx, |vim, _: &SwitchToNormalMode, window, cx| {
+                // Escaping from normal mode drops a pending `cx` exchange region.
+                if vim.mode == Mode::Normal {
+                    vim.update_editor(cx, |_, editor, cx| {
+                        editor.clear_background_highlights::<replace::VimExchange>(cx);
+                    });
+                }
                 if HelixModeSetting::get_global(cx).0 {
                     vim.switch_mode(Mode::HelixNormal, false, window, cx)
                 } else {
@@ -662,6 +679,14 @@ impl Vim {
                 vim.push_operator(Operator::AutoIndent, cx)
             });
 
+            Vim::action(editor, cx, |vim, _: &PushTabsToSpaces, _window, cx| {
+                vim.push_operator(Operator::TabsToSpaces, cx)
+            });
+
+            Vim::action(editor, cx, |vim, _: &PushSpacesToTabs, _window, cx| {
+                vim.push_operator(Operator::SpacesToTabs, cx)
+            });
+
             Vim::action(editor, cx, |vim, _: &PushRewrap, _window, cx| {
                 vim.push_operator(Operator::Rewrap, cx)
             });
@@ -946,6 +971,7 @@ impl Vim {
         self.cancel_running_command(window, cx);
         if mode == Mode::Normal || mode != last_mode {
             self.current_tx.take();
+            self.insert_session_tx.take();
             self.current_anchor.take();
             self.update_editor(cx, |_, editor, _| {
                 editor.clear_selection_drag_state();
@@ -1443,6 +1469,11 @@ impl Vim {
         } else {
             self.mode
         };
+        if (self.mode == Mode::Insert || self.mode == Mode::Replace)
+            && self.insert_session_tx.is_none()
+        {
+            self.insert_session_tx = Some(transaction_id);
+        }
         if mode == Mode::VisualLine || mode == Mode::VisualBlock {
             self.undo_modes.insert(transaction_id, mode);
         }